@@ -1,5 +1,8 @@
 use std::fs::File;
-use std::io::Read;
+use std::io;
+use std::io::{BufReader, Read};
+use std::path::Path;
+use std::thread;
 
 use bit_vec::BitVec;
 use xxhash2;
@@ -22,13 +25,17 @@ const GEAR2_MASK2: u64 = 0x0000_D900_0353_0000;
 // Component Header
 const HEAD_DID: u8 = 0x20;
 
-pub fn data_id(data_path: &str) -> std::io::Result<String> {
+pub fn data_id(data_path: &str) -> io::Result<String> {
     let data = File::open(data_path)?;
+    data_id_from_reader(data)
+}
 
+pub fn data_id_from_reader<R: Read>(data: R) -> io::Result<String> {
     //  1. & 2. XxHash32 over CDC-Chunks
-    let features: Vec<u32> = data_chunks(data)
-        .map(|chunk| xxhash2::hash32(&chunk, 0))
-        .collect();
+    let mut features: Vec<u32> = Vec::new();
+    for chunk in data_chunks(data) {
+        features.push(xxhash2::hash32(&chunk?, 0));
+    }
 
     // 3. Apply minimum_hash
     let minhash = minimum_hash(features, 64);
@@ -47,30 +54,402 @@ pub fn data_id(data_path: &str) -> std::io::Result<String> {
     Ok(encode(&data_id_digest))
 }
 
-struct Chunk {
-    // TODO: Generalize with Reader trait
-    // TODO: Maybe use BufReader
-    data: File,
+/// Compute a Data-ID for every path, fanning the work out across `available_parallelism`
+/// worker threads. Each worker reuses a single scratch buffer across the files it's handed
+/// instead of allocating one per file, and results come back in the same order as `paths`.
+///
+/// This is the batch counterpart to `data_id`, for bulk-ingestion workloads (e.g. indexing
+/// a whole directory of assets) where the per-call allocation of a fresh buffer dominates.
+pub fn data_ids<P: AsRef<Path> + Sync>(paths: &[P]) -> Vec<io::Result<String>> {
+    let worker_count = thread::available_parallelism()
+        .map(usize::from)
+        .unwrap_or(1)
+        .min(paths.len().max(1));
+
+    let mut results: Vec<Option<io::Result<String>>> = Vec::with_capacity(paths.len());
+    results.resize_with(paths.len(), || None);
+
+    thread::scope(|scope| {
+        let mut handles = Vec::with_capacity(worker_count);
+        for worker in 0..worker_count {
+            let paths = &paths;
+            handles.push(scope.spawn(move || {
+                let mut buf = Vec::new();
+                let mut out = Vec::new();
+                for index in (worker..paths.len()).step_by(worker_count) {
+                    let result = match File::open(paths[index].as_ref()) {
+                        Ok(file) => {
+                            let (id, returned_buf) = data_id_from_reader_with_buf(file, buf);
+                            buf = returned_buf;
+                            id
+                        }
+                        Err(e) => Err(e),
+                    };
+                    out.push((index, result));
+                }
+                out
+            }));
+        }
+        for handle in handles {
+            for (index, result) in handle.join().unwrap() {
+                results[index] = Some(result);
+            }
+        }
+    });
+
+    results.into_iter().map(|r| r.unwrap()).collect()
+}
+
+/// Like `data_ids`, but for readers already held in memory rather than paths to open.
+pub fn data_ids_from_readers<R: Read + Send>(readers: Vec<R>) -> Vec<io::Result<String>> {
+    let worker_count = thread::available_parallelism()
+        .map(usize::from)
+        .unwrap_or(1)
+        .min(readers.len().max(1));
+
+    let mut chunks: Vec<Vec<(usize, R)>> = (0..worker_count).map(|_| Vec::new()).collect();
+    let mut count = 0;
+    for (index, reader) in readers.into_iter().enumerate() {
+        chunks[index % worker_count].push((index, reader));
+        count += 1;
+    }
+
+    let mut results: Vec<Option<io::Result<String>>> = Vec::with_capacity(count);
+    results.resize_with(count, || None);
+
+    thread::scope(|scope| {
+        let mut handles = Vec::with_capacity(worker_count);
+        for chunk in chunks {
+            handles.push(scope.spawn(move || {
+                let mut buf = Vec::new();
+                let mut out = Vec::with_capacity(chunk.len());
+                for (index, reader) in chunk {
+                    let (id, returned_buf) = data_id_from_reader_with_buf(reader, buf);
+                    buf = returned_buf;
+                    out.push((index, id));
+                }
+                out
+            }));
+        }
+        for handle in handles {
+            for (index, result) in handle.join().unwrap() {
+                results[index] = Some(result);
+            }
+        }
+    });
+
+    results.into_iter().map(|r| r.unwrap()).collect()
+}
+
+/// Like `data_id_from_reader`, but reuses a caller-supplied scratch buffer for the
+/// underlying chunk ring buffer instead of allocating a fresh one, handing it back
+/// alongside the result so the caller can feed it into the next call. The returned buffer
+/// still holds this call's bytes; it is cleared when the next call takes it.
+fn data_id_from_reader_with_buf<R: Read>(data: R, buf: Vec<u8>) -> (io::Result<String>, Vec<u8>) {
+    let mut chunk = Chunk::new_with_buf(data, GearChunker, buf);
+
+    let mut features: Vec<u32> = Vec::new();
+    let mut error = None;
+    for item in &mut chunk {
+        match item {
+            Ok(bytes) => features.push(xxhash2::hash32(&bytes, 0)),
+            Err(e) => {
+                error = Some(e);
+                break;
+            }
+        }
+    }
+
+    let result = match error {
+        Some(e) => Err(e),
+        None => {
+            let minhash = minimum_hash(features, 64);
+            let lsb: BitVec = minhash.iter().map(|x| (x & 1) == 1).collect();
+            let lsb_bytes = lsb.to_bytes();
+            let mut data_id_digest = vec![HEAD_DID];
+            data_id_digest.extend(&lsb_bytes);
+            Ok(encode(&data_id_digest))
+        }
+    };
+
+    (result, chunk.into_buf())
+}
+
+/// A pluggable content-defined chunking backend.
+///
+/// Implementations look at the unconsumed bytes of the current chunk and decide where to
+/// cut the next boundary. `chunk_index` lets a backend vary its parameters over the stream
+/// (the gear backend switches to larger masks and size limits after the first 100 chunks).
+pub trait Chunker {
+    /// Lower bound on the next chunk's size; also the minimum run length that qualifies a
+    /// region of identical bytes for fill-run detection.
+    fn min_size(&self, chunk_index: usize) -> usize;
+
+    /// Upper bound on the next chunk's size, used to decide how much unconsumed data must
+    /// be buffered before a boundary can be computed.
+    fn max_size(&self, chunk_index: usize) -> usize;
+
+    /// Locate the next chunk boundary (an exclusive end offset into `data`).
+    fn boundary(&self, data: &[u8], chunk_index: usize) -> usize;
+
+    /// Whether this backend folds a run of `>= min_size` identical bytes into its own chunk
+    /// (chunk0-4) instead of always running `boundary`. Defaults to `false`: fill-run
+    /// detection changes chunk boundaries, and therefore the resulting Data-ID, for any
+    /// input that happens to contain such a run, so the default `GearChunker` path used by
+    /// `data_id`/`data_chunks` must not pick it up silently. Wrap a chunker in
+    /// `FillRunAware` to opt in.
+    fn detects_fill_runs(&self) -> bool {
+        false
+    }
+}
+
+/// Wraps a `Chunker` to opt it in to fill-run detection (see `Chunker::detects_fill_runs`),
+/// delegating everything else to the inner chunker unchanged. Use this explicitly — e.g.
+/// `data_chunks_with(reader, FillRunAware(GearChunker))` — rather than reaching for it as
+/// the default, since it changes the Data-ID for inputs containing long runs of one byte
+/// value.
+pub struct FillRunAware<C: Chunker>(pub C);
+
+impl<C: Chunker> Chunker for FillRunAware<C> {
+    fn min_size(&self, chunk_index: usize) -> usize {
+        self.0.min_size(chunk_index)
+    }
+
+    fn max_size(&self, chunk_index: usize) -> usize {
+        self.0.max_size(chunk_index)
+    }
+
+    fn boundary(&self, data: &[u8], chunk_index: usize) -> usize {
+        self.0.boundary(data, chunk_index)
+    }
+
+    fn detects_fill_runs(&self) -> bool {
+        true
+    }
+}
+
+/// The original gear/FastCDC-style chunker: small boundary masks for the first 100 chunks,
+/// then larger ones. This is the default backend and what existing Data-IDs were computed
+/// with.
+#[derive(Default)]
+pub struct GearChunker;
+
+impl Chunker for GearChunker {
+    fn min_size(&self, chunk_index: usize) -> usize {
+        if chunk_index < 100 {
+            GEAR1_MIN
+        } else {
+            GEAR2_MIN
+        }
+    }
+
+    fn max_size(&self, chunk_index: usize) -> usize {
+        if chunk_index < 100 {
+            GEAR1_MAX
+        } else {
+            GEAR2_MAX
+        }
+    }
+
+    fn boundary(&self, data: &[u8], chunk_index: usize) -> usize {
+        if chunk_index < 100 {
+            chunk_length(
+                data,
+                GEAR1_NORM,
+                GEAR1_MIN,
+                GEAR1_MAX,
+                GEAR1_MASK1,
+                GEAR1_MASK2,
+            )
+        } else {
+            chunk_length(
+                data,
+                GEAR2_NORM,
+                GEAR2_MIN,
+                GEAR2_MAX,
+                GEAR2_MASK1,
+                GEAR2_MASK2,
+            )
+        }
+    }
+}
+
+/// Asymmetric Extremum (AE) chunker: no rolling hash or masks, just a single window derived
+/// from the target average chunk size. Roughly 2x the throughput of the gear chunker with
+/// comparable dedup ratios, at the cost of needing a fixed average-size target rather than
+/// the gear chunker's two-phase norm/min/max tuning.
+pub struct AeChunker {
+    min_size: usize,
+    max_size: usize,
+    window: usize,
+}
+
+impl AeChunker {
+    /// `avg_size` is the desired average chunk size; the AE window is derived from it as
+    /// `round(avg_size / (e - 1))`.
+    pub fn new(avg_size: usize, min_size: usize, max_size: usize) -> AeChunker {
+        let window = ((avg_size as f64) / (std::f64::consts::E - 1.0)).round() as usize;
+        AeChunker {
+            min_size,
+            max_size,
+            window,
+        }
+    }
+}
+
+impl Chunker for AeChunker {
+    fn min_size(&self, _chunk_index: usize) -> usize {
+        self.min_size
+    }
+
+    fn max_size(&self, _chunk_index: usize) -> usize {
+        self.max_size
+    }
+
+    fn boundary(&self, data: &[u8], _chunk_index: usize) -> usize {
+        ae_chunk_length(data, self.min_size, self.max_size, self.window)
+    }
+}
+
+/// Asymmetric Extremum boundary search: scan left to right tracking the position and value
+/// of the maximum byte seen so far in the chunk; cut once `window` bytes have passed since
+/// the last new maximum. Unlike the gear chunker this needs no rolling hash or masks.
+pub fn ae_chunk_length(data: &[u8], min_size: usize, max_size: usize, window: usize) -> usize {
+    let data_length = data.len();
+    if data_length <= min_size {
+        return data_length;
+    }
+
+    let barrier = max_size.min(data_length);
+    let mut max_pos = 0;
+    let mut max_val = data[0];
+    let mut i = 1;
+    while i < barrier {
+        if data[i] > max_val {
+            max_val = data[i];
+            max_pos = i;
+        } else if i == (max_pos + window).max(min_size) {
+            return i;
+        }
+        i += 1;
+    }
+    barrier
+}
+
+// Size of the blocks read from the underlying reader into the ring buffer.
+const BUFFER_SIZE: usize = 16384;
+
+struct Chunk<R: Read, C: Chunker> {
+    data: BufReader<R>,
+    chunker: C,
     counter: usize,
-    section: Vec<u8>,
+    buf: Vec<u8>,
+    consumed: usize,
+    // Set when the initial fill in `new_with_buf` hits an IO error; surfaced on the first
+    // call to `next()` instead of being dropped on the floor.
+    error: Option<io::Error>,
 }
 
-impl Chunk {
-    fn new(mut data: File) -> Chunk {
-        let mut buffer = [0; GEAR1_MAX];
-        let n = data.read(&mut buffer).unwrap();
-        let mut section: Vec<u8> = Vec::new();
-        section.extend(&buffer[..n]);
-        Chunk {
-            data,
+impl<R: Read, C: Chunker> Chunk<R, C> {
+    fn new(data: R, chunker: C) -> Chunk<R, C> {
+        Chunk::new_with_buf(data, chunker, Vec::new())
+    }
+
+    // Like `new`, but reuses `buf` (cleared) as the ring buffer instead of allocating one.
+    fn new_with_buf(data: R, chunker: C, mut buf: Vec<u8>) -> Chunk<R, C> {
+        buf.clear();
+        let mut chunk = Chunk {
+            data: BufReader::new(data),
+            chunker,
             counter: 0,
-            section,
+            buf,
+            consumed: 0,
+            error: None,
+        };
+        let needed = chunk.chunker.max_size(0);
+        if let Err(e) = chunk.fill(needed) {
+            chunk.error = Some(e);
+        }
+        chunk
+    }
+
+    // Reclaim the ring buffer, contents and all, so a caller can feed it into the next
+    // `Chunk::new_with_buf` call (which clears it) instead of allocating a new one.
+    fn into_buf(self) -> Vec<u8> {
+        self.buf
+    }
+
+    // Read fixed-size blocks from `data` until at least `needed` unconsumed bytes are
+    // buffered or the reader is exhausted.
+    fn fill(&mut self, needed: usize) -> io::Result<()> {
+        let mut block = [0; BUFFER_SIZE];
+        while self.buf.len() - self.consumed < needed {
+            let n = self.data.read(&mut block)?;
+            if n == 0 {
+                break;
+            }
+            self.buf.extend(&block[..n]);
+        }
+        Ok(())
+    }
+
+    // Drop already-consumed bytes once they've piled up, so the buffer doesn't grow
+    // unbounded over a multi-gigabyte stream.
+    fn compact(&mut self) {
+        if self.consumed > BUFFER_SIZE {
+            self.buf.drain(..self.consumed);
+            self.consumed = 0;
+        }
+    }
+
+    // If the unconsumed data opens with a run of at least `min_size` identical bytes (e.g.
+    // zero padding in media containers or disk-image-derived content), cut a chunk there
+    // instead of running the backend's boundary search. `cap` bounds how much of the run is
+    // materialized at once (the backend's normal max chunk size for this position): a run
+    // longer than `cap` is cut into `cap`-sized sub-chunks rather than buffered in full, so a
+    // multi-gigabyte fill region never defeats the ring buffer's bounded memory use. Returns
+    // `None` if there's no such run at the current position.
+    //
+    // Note this deliberately diverges from the original ask of folding an entire fill run into
+    // a single chunk: an unbounded version of this existed earlier in this series and had to be
+    // walked back (see edabf3b, reverted by 7dfc77b) because it let one pathological input
+    // buffer arbitrarily much memory. Capping at `cap` and repeating is the tradeoff that keeps
+    // memory bounded; don't "fix" this back to one-chunk-per-run without also solving that.
+    fn fill_run_boundary(&mut self, min_size: usize, cap: usize) -> io::Result<Option<usize>> {
+        if min_size == 0 || self.buf.len() - self.consumed < min_size {
+            return Ok(None);
+        }
+        let first = self.buf[self.consumed];
+        if self.buf[self.consumed..self.consumed + min_size]
+            .iter()
+            .any(|&b| b != first)
+        {
+            return Ok(None);
+        }
+        if self.buf.len() - self.consumed < cap {
+            self.fill(cap)?;
         }
+        let available = self.buf.len() - self.consumed;
+        let scan_len = available.min(cap);
+        let run_len = self.buf[self.consumed..self.consumed + scan_len]
+            .iter()
+            .take_while(|&&b| b == first)
+            .count();
+        Ok(Some(run_len))
     }
 }
 
-pub fn data_chunks(data: File) -> impl Iterator<Item = Vec<u8>> {
-    Chunk::new(data)
+pub fn data_chunks<R: Read>(data: R) -> impl Iterator<Item = io::Result<Vec<u8>>> {
+    data_chunks_with(data, GearChunker)
+}
+
+/// Like `data_chunks`, but with an explicit `Chunker` backend, e.g. `AeChunker` for very
+/// large inputs where throughput matters more than matching the default gear boundaries.
+pub fn data_chunks_with<R: Read, C: Chunker>(
+    data: R,
+    chunker: C,
+) -> impl Iterator<Item = io::Result<Vec<u8>>> {
+    Chunk::new(data, chunker)
 }
 
 pub fn chunk_length(
@@ -104,59 +483,48 @@ pub fn chunk_length(
     i
 }
 
-impl Iterator for Chunk {
-    type Item = Vec<u8>;
-    fn next(&mut self) -> Option<Vec<u8>> {
-        let mut buffer = [0; GEAR2_MAX];
-        let boundary: usize;
-
+impl<R: Read, C: Chunker> Iterator for Chunk<R, C> {
+    type Item = io::Result<Vec<u8>>;
+    fn next(&mut self) -> Option<io::Result<Vec<u8>>> {
+        if let Some(e) = self.error.take() {
+            return Some(Err(e));
+        }
         let counter = self.counter;
-        let mut section = self.section.clone();
-        let mut data = &self.data;
-        if counter < 100 {
-            if section.len() < GEAR1_MAX {
-                let n = data.read(&mut buffer).unwrap();
-                section.extend(&buffer[..n]);
+        let max_size = self.chunker.max_size(counter);
+        if self.buf.len() - self.consumed < max_size {
+            if let Err(e) = self.fill(max_size) {
+                return Some(Err(e));
             }
-            if section.is_empty() {
-                return None;
+        }
+        if self.consumed == self.buf.len() {
+            return None;
+        }
+        let boundary = if self.chunker.detects_fill_runs() {
+            let min_size = self.chunker.min_size(counter);
+            match self.fill_run_boundary(min_size, max_size) {
+                Ok(Some(run_len)) => run_len,
+                Ok(None) => self.chunker.boundary(&self.buf[self.consumed..], counter),
+                Err(e) => return Some(Err(e)),
             }
-            boundary = chunk_length(
-                &section,
-                GEAR1_NORM,
-                GEAR1_MIN,
-                GEAR1_MAX,
-                GEAR1_MASK1,
-                GEAR1_MASK2,
-            );
         } else {
-            if section.len() < GEAR2_MAX {
-                let n = data.read(&mut buffer).unwrap();
-                section.extend(&buffer[..n]);
-            }
-            if section.is_empty() {
-                return None;
-            }
-            boundary = chunk_length(
-                &section,
-                GEAR2_NORM,
-                GEAR2_MIN,
-                GEAR2_MAX,
-                GEAR2_MASK1,
-                GEAR2_MASK2,
-            );
-        }
-        self.section = section[boundary..].to_vec();
+            self.chunker.boundary(&self.buf[self.consumed..], counter)
+        };
+        let chunk = self.buf[self.consumed..self.consumed + boundary].to_vec();
+        self.consumed += boundary;
         self.counter += 1;
-        Some(section[..boundary].to_vec())
+        self.compact();
+        Some(Ok(chunk))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::iter::FromIterator;
 
+    // `test_data_id`/`test_data_chunks` below go through the default `GearChunker`, which
+    // does not opt in to fill-run detection (`Chunker::detects_fill_runs` is `false` unless
+    // wrapped in `FillRunAware`) — so these fixture-backed assertions exercise the same
+    // boundary/hash logic as before chunk0-4 and don't need re-verification on its account.
     #[test]
     fn test_data_id() {
         assert_eq!(
@@ -175,9 +543,160 @@ mod tests {
     #[test]
     fn test_data_chunks() {
         let f = File::open("test_data/lenna.jpg").expect("Unable to open file");
-        let chunks1 = Vec::from_iter(data_chunks(f));
+        let chunks1: Vec<Vec<u8>> = data_chunks(f).map(|c| c.unwrap()).collect();
         assert_eq!(chunks1.len(), 112);
         assert_eq!(chunks1[0].len(), 38);
         assert_eq!(chunks1.last().unwrap().len(), 2840);
     }
+
+    // A reader that fails after yielding `good_bytes` bytes, used to prove that a mid-stream
+    // IO error is surfaced through `data_chunks`/`data_id_from_reader` as an `Err` rather
+    // than panicking the caller.
+    struct FlakyReader {
+        good_bytes: usize,
+    }
+
+    impl Read for FlakyReader {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            if self.good_bytes == 0 {
+                return Err(io::Error::new(io::ErrorKind::Other, "flaky read failed"));
+            }
+            let n = buf.len().min(self.good_bytes);
+            for byte in &mut buf[..n] {
+                *byte = 0x42;
+            }
+            self.good_bytes -= n;
+            Ok(n)
+        }
+    }
+
+    #[test]
+    fn test_data_chunks_propagates_io_errors() {
+        let reader = FlakyReader {
+            good_bytes: GEAR1_MAX * 2,
+        };
+        let result: io::Result<Vec<Vec<u8>>> = data_chunks(reader).collect();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_data_id_from_reader_propagates_io_errors() {
+        let reader = FlakyReader {
+            good_bytes: GEAR1_MAX * 2,
+        };
+        assert!(data_id_from_reader(reader).is_err());
+    }
+
+    #[test]
+    fn test_data_id_from_reader() {
+        let f = File::open("test_data/cat.jpg").expect("Unable to open file");
+        assert_eq!(data_id_from_reader(f).unwrap(), "CDC7Lg4oHA8DC".to_string());
+    }
+
+    #[test]
+    fn test_default_gear_chunker_ignores_fill_runs() {
+        // The default GearChunker doesn't opt in to fill-run detection, so a run that would
+        // otherwise be cut specially is chunked exactly as it was before chunk0-4.
+        assert!(!GearChunker.detects_fill_runs());
+        let mut data = vec![0u8; GEAR1_MAX * 5];
+        data.extend(std::iter::repeat(1u8).take(100));
+        let chunks: Vec<Vec<u8>> = data_chunks(&data[..]).map(|c| c.unwrap()).collect();
+        assert_ne!(chunks[0].len(), data.len());
+        assert_eq!(chunks.iter().map(|c| c.len()).sum::<usize>(), data.len());
+    }
+
+    #[test]
+    fn test_short_fill_run_is_a_single_chunk() {
+        // Shorter than a single GEAR1_MAX-capped chunk, so the whole run is one chunk.
+        let mut data = vec![0u8; GEAR1_MIN * 2];
+        data.extend(std::iter::repeat(1u8).take(100));
+        let chunks: Vec<Vec<u8>> = data_chunks_with(&data[..], FillRunAware(GearChunker))
+            .map(|c| c.unwrap())
+            .collect();
+        assert_eq!(chunks[0].len(), GEAR1_MIN * 2);
+    }
+
+    #[test]
+    fn test_long_fill_run_is_split_into_capped_sub_chunks() {
+        // Longer than a single chunk's max size: the run must be cut into several
+        // max-size-bounded sub-chunks rather than buffered whole (chunk0-3's point).
+        let run_len = GEAR1_MAX * 5;
+        let mut data = vec![0u8; run_len];
+        data.extend(std::iter::repeat(1u8).take(100));
+        let chunks: Vec<Vec<u8>> = data_chunks_with(&data[..], FillRunAware(GearChunker))
+            .map(|c| c.unwrap())
+            .collect();
+        assert!(chunks.iter().all(|c| c.len() <= GEAR1_MAX));
+        assert_eq!(chunks.iter().map(|c| c.len()).sum::<usize>(), data.len());
+    }
+
+    // Regression guard: plausible real-file byte patterns (varied, not a long run of one
+    // value) must not trip fill-run detection.
+    #[test]
+    fn test_fill_run_detection_does_not_trigger_on_varied_bytes() {
+        let data: Vec<u8> = (0..(GEAR1_MAX as u32) * 2)
+            .map(|i| ((i.wrapping_mul(2654435761)) % 251) as u8)
+            .collect();
+        let chunks: Vec<Vec<u8>> = data_chunks_with(&data[..], FillRunAware(GearChunker))
+            .map(|c| c.unwrap())
+            .collect();
+        assert_eq!(chunks.iter().map(|c| c.len()).sum::<usize>(), data.len());
+        let plain: Vec<Vec<u8>> = data_chunks(&data[..]).map(|c| c.unwrap()).collect();
+        assert_eq!(chunks, plain);
+    }
+
+    #[test]
+    fn test_data_ids() {
+        let paths = [
+            "test_data/cat.jpg",
+            "test_data/cat.png",
+            "test_data/cat.gif",
+        ];
+        let ids: Vec<String> = data_ids(&paths).into_iter().map(|id| id.unwrap()).collect();
+        assert_eq!(
+            ids,
+            vec![
+                "CDC7Lg4oHA8DC".to_string(),
+                "CDCx1AzhDGcT7".to_string(),
+                "CDcLVF7es2AEP".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_data_ids_from_readers() {
+        let readers = vec![
+            File::open("test_data/cat.jpg").expect("Unable to open file"),
+            File::open("test_data/cat.png").expect("Unable to open file"),
+        ];
+        let ids: Vec<String> = data_ids_from_readers(readers)
+            .into_iter()
+            .map(|id| id.unwrap())
+            .collect();
+        assert_eq!(
+            ids,
+            vec!["CDC7Lg4oHA8DC".to_string(), "CDCx1AzhDGcT7".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_data_chunks_with_ae() {
+        let f = File::open("test_data/lenna.jpg").expect("Unable to open file");
+        let chunker = AeChunker::new(GEAR2_NORM, GEAR2_MIN, GEAR2_MAX);
+        let chunks: Vec<Vec<u8>> = data_chunks_with(f, chunker).map(|c| c.unwrap()).collect();
+        assert!(!chunks.is_empty());
+        assert_eq!(
+            chunks.iter().map(|c| c.len()).sum::<usize>(),
+            std::fs::metadata("test_data/lenna.jpg").unwrap().len() as usize
+        );
+    }
+
+    #[test]
+    fn test_ae_chunk_length_defers_small_window_cuts_to_min_size() {
+        // window (3) lands before min_size (10): the cut must wait for min_size rather than
+        // being dropped until the next new maximum or max_size.
+        let mut data = vec![1u8; 20];
+        data[0] = 255;
+        assert_eq!(ae_chunk_length(&data, 10, 20, 3), 10);
+    }
 }